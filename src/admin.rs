@@ -0,0 +1,267 @@
+//! 极简的 Kafka 管理协议实现。
+//!
+//! `kafka` crate（kafka-rust）没有提供 CreateTopics/DeleteTopics 这类 admin API，
+//! 这里按照 Kafka 线协议手工编码/解析 CreateTopics v0 与 DeleteTopics v0 的请求/响应帧，
+//! 通过一条独立的 TCP 连接直接发给 broker。
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+const API_KEY_METADATA: i16 = 3;
+const API_KEY_CREATE_TOPICS: i16 = 19;
+const API_KEY_DELETE_TOPICS: i16 = 20;
+const API_VERSION: i16 = 0;
+const METADATA_API_VERSION: i16 = 1;
+const CLIENT_ID: &str = "mtools-kafka-admin";
+
+/// 创建一个主题所需的参数
+pub struct CreatableTopic {
+    pub name: String,
+    pub num_partitions: i32,
+    pub replication_factor: i16,
+    pub config_map: HashMap<String, String>,
+}
+
+/// 单个主题的 broker 返回结果
+pub struct TopicResult {
+    pub topic: String,
+    pub error_code: i16,
+}
+
+impl TopicResult {
+    pub fn is_ok(&self) -> bool {
+        self.error_code == 0
+    }
+}
+
+struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    fn new() -> Self {
+        Encoder { buf: Vec::new() }
+    }
+
+    fn i16(&mut self, v: i16) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn i32(&mut self, v: i32) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn string(&mut self, s: &str) {
+        self.i16(s.len() as i16);
+        self.buf.extend_from_slice(s.as_bytes());
+    }
+
+    fn nullable_string(&mut self, s: &str) {
+        self.string(s);
+    }
+}
+
+/// 有边界检查的响应解析器；`auth.rs` 的 SASL 握手解析也复用它，
+/// 避免裸用下标访问短响应/错误响应时越界 panic
+pub(crate) struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+fn truncated() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "响应帧数据不完整")
+}
+
+impl<'a> Decoder<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Decoder { buf, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).ok_or_else(truncated)?;
+        if end > self.buf.len() {
+            return Err(truncated());
+        }
+        let slice = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    pub(crate) fn i16(&mut self) -> io::Result<i16> {
+        let b = self.take(2)?;
+        Ok(i16::from_be_bytes([b[0], b[1]]))
+    }
+
+    pub(crate) fn i32(&mut self) -> io::Result<i32> {
+        let b = self.take(4)?;
+        Ok(i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn string(&mut self) -> io::Result<String> {
+        let len = self.i16()? as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).into_owned())
+    }
+
+    /// 可空字符串：长度为 -1 表示 null
+    fn nullable_string(&mut self) -> io::Result<Option<String>> {
+        let len = self.i16()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(self.take(len as usize)?).into_owned(),
+        ))
+    }
+}
+
+fn request_header(encoder: &mut Encoder, api_key: i16, api_version: i16, correlation_id: i32) {
+    encoder.i16(api_key);
+    encoder.i16(api_version);
+    encoder.i32(correlation_id);
+    encoder.nullable_string(CLIENT_ID);
+}
+
+fn send_request(host: &str, body: Vec<u8>) -> io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect(host)?;
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(10)))?;
+
+    let mut framed = Vec::with_capacity(4 + body.len());
+    framed.extend_from_slice(&(body.len() as i32).to_be_bytes());
+    framed.extend_from_slice(&body);
+    stream.write_all(&framed)?;
+
+    let mut size_buf = [0u8; 4];
+    stream.read_exact(&mut size_buf)?;
+    let size = i32::from_be_bytes(size_buf) as usize;
+    let mut resp = vec![0u8; size];
+    stream.read_exact(&mut resp)?;
+    Ok(resp)
+}
+
+/// 发送 CreateTopics 请求。CreateTopics/DeleteTopics 只有 controller 会处理，
+/// 发给其他 broker 会返回 NOT_CONTROLLER（error_code 41），所以先用 Metadata 请求
+/// 在 `hosts` 中探测出 controller 的地址，再把请求发过去。
+pub fn create_topics(
+    hosts: &[String],
+    topics: &[CreatableTopic],
+    timeout_ms: i32,
+) -> io::Result<Vec<TopicResult>> {
+    let controller = discover_controller(hosts)?;
+    let mut encoder = Encoder::new();
+    request_header(&mut encoder, API_KEY_CREATE_TOPICS, API_VERSION, 1);
+
+    encoder.i32(topics.len() as i32);
+    for topic in topics {
+        encoder.string(topic.name.as_str());
+        encoder.i32(topic.num_partitions);
+        encoder.i16(topic.replication_factor);
+        // replica_assignment：留空数组，交给 broker 自动分配
+        encoder.i32(0);
+        encoder.i32(topic.config_map.len() as i32);
+        for (key, value) in &topic.config_map {
+            encoder.string(key.as_str());
+            encoder.string(value.as_str());
+        }
+    }
+    encoder.i32(timeout_ms);
+
+    let resp = send_request(controller.as_str(), encoder.buf)?;
+    let mut decoder = Decoder::new(&resp);
+    decoder.i32()?; // correlation_id
+
+    let count = decoder.i32()?;
+    let mut results = Vec::with_capacity(count.max(0) as usize);
+    for _ in 0..count {
+        let topic = decoder.string()?;
+        let error_code = decoder.i16()?;
+        results.push(TopicResult { topic, error_code });
+    }
+    Ok(results)
+}
+
+/// 发送 DeleteTopics 请求，同样先探测 controller 地址，参见 [`create_topics`]
+pub fn delete_topics(
+    hosts: &[String],
+    topics: &[String],
+    timeout_ms: i32,
+) -> io::Result<Vec<TopicResult>> {
+    let controller = discover_controller(hosts)?;
+    let mut encoder = Encoder::new();
+    request_header(&mut encoder, API_KEY_DELETE_TOPICS, API_VERSION, 1);
+
+    encoder.i32(topics.len() as i32);
+    for topic in topics {
+        encoder.string(topic.as_str());
+    }
+    encoder.i32(timeout_ms);
+
+    let resp = send_request(controller.as_str(), encoder.buf)?;
+    let mut decoder = Decoder::new(&resp);
+    decoder.i32()?; // correlation_id
+
+    let count = decoder.i32()?;
+    let mut results = Vec::with_capacity(count.max(0) as usize);
+    for _ in 0..count {
+        let topic = decoder.string()?;
+        let error_code = decoder.i16()?;
+        results.push(TopicResult { topic, error_code });
+    }
+    Ok(results)
+}
+
+struct MetadataBroker {
+    node_id: i32,
+    host: String,
+    port: i32,
+}
+
+/// 发送一次只要 broker 列表的 Metadata 请求（topics 数组传空，不关心任何主题的分区信息）
+fn fetch_controller_candidates(host: &str) -> io::Result<(Vec<MetadataBroker>, i32)> {
+    let mut encoder = Encoder::new();
+    request_header(&mut encoder, API_KEY_METADATA, METADATA_API_VERSION, 1);
+    encoder.i32(0); // topics：空数组
+
+    let resp = send_request(host, encoder.buf)?;
+    let mut decoder = Decoder::new(&resp);
+    decoder.i32()?; // correlation_id
+
+    let broker_count = decoder.i32()?;
+    let mut brokers = Vec::with_capacity(broker_count.max(0) as usize);
+    for _ in 0..broker_count {
+        let node_id = decoder.i32()?;
+        let host = decoder.string()?;
+        let port = decoder.i32()?;
+        decoder.nullable_string()?; // rack，管理操作用不到
+        brokers.push(MetadataBroker {
+            node_id,
+            host,
+            port,
+        });
+    }
+    let controller_id = decoder.i32()?;
+    Ok((brokers, controller_id))
+}
+
+/// 在 `bootstrap_hosts` 里找一台能连上的 broker 查询集群元数据，解析出 controller 的地址
+fn discover_controller(bootstrap_hosts: &[String]) -> io::Result<String> {
+    let mut last_err = None;
+    for host in bootstrap_hosts {
+        match fetch_controller_candidates(host.as_str()) {
+            Ok((brokers, controller_id)) => {
+                return brokers
+                    .into_iter()
+                    .find(|b| b.node_id == controller_id)
+                    .map(|b| format!("{}:{}", b.host, b.port))
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::NotFound, "元数据中未找到 controller broker")
+                    });
+            }
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err
+        .unwrap_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "没有可用的 broker 地址")))
+}
@@ -1,5 +1,14 @@
+mod admin;
+mod auth;
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc, Arc,
+};
+use std::thread;
 use std::time::Duration;
 use std::{
+    collections::HashMap,
     fs::{self, File, OpenOptions},
     io::{Read, Write},
     path::PathBuf,
@@ -9,13 +18,16 @@ use eframe::egui::{
     self, Align, CollapsingHeader, Color32, FontData, FontFamily, Label, Layout, RichText, Sense,
 };
 use egui_extras::{Column, TableBuilder};
-use kafka::client::{KafkaClient, PartitionOffset};
+use kafka::client::{KafkaClient, PartitionOffset, SecurityConfig};
 use kafka::consumer::GroupOffsetStorage;
-use kafka::producer::{Producer, Record, RequiredAcks};
+use kafka::producer::{Compression, Producer, Record, RequiredAcks};
 use kafka::{
     consumer::{Consumer, FetchOffset},
     producer::AsBytes,
 };
+use keyring::Entry;
+use openssl::ssl::{SslConnector, SslFiletype, SslMethod};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 static APP_NAME: &str = "Kafka Tool";
@@ -25,19 +37,189 @@ static KAFKA_GROUP_ID: &str = "mtools";
 struct ToolApp {
     config: ToolConfig,
     temp_config: KafkaConfig,
+    temp_password_input: String,
     current_config: KafkaConfig,
     panel_id: String,
     list_panel_id: Option<String>,
     current_topic: String,
     current_offset_type: String,
+    subscribe_mode: SubscribeMode,
+    subscribe_topics_input: String,
+    subscribe_pattern: String,
     current_messages: Vec<KafkaMessage>,
     partition_offsets: Vec<PartitionOffset>,
     value_filter: String,
+    filter_is_regex: bool,
+    filter_include_key: bool,
+    value_decoder: ValueDecoder,
     send_value: String,
+    send_key: String,
+    send_batch_by_line: bool,
+    send_partition_mode: PartitionMode,
+    send_partition: i32,
     kafka_producer: Option<Producer>,
+    kafka_producer_signature: Option<(AcksMode, u64, CompressionMode)>,
     poll_rows: usize,
     commit_offset: CommitOffset,
     send_message: String,
+    lag_group: String,
+    partition_lags: Vec<PartitionLag>,
+    lag_error_message: String,
+    data_lag_threshold: i64,
+    data_partition_lags: Vec<PartitionLag>,
+    new_topic_name: String,
+    new_topic_partitions: i32,
+    new_topic_replication_factor: i16,
+    new_topic_configs: Vec<(String, String)>,
+    delete_topic_name: String,
+    topic_admin_message: String,
+    streaming: bool,
+    stream_stop: Option<Arc<AtomicBool>>,
+    stream_rx: Option<mpsc::Receiver<KafkaMessage>>,
+    stream_cap: usize,
+    stream_error_message: String,
+}
+
+#[derive(Default, Debug, Clone)]
+struct PartitionLag {
+    partition: i32,
+    earliest: i64,
+    latest: i64,
+    committed: i64,
+    lag: i64,
+}
+
+/// 发送数据时选择的分区策略
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PartitionMode {
+    /// 轮询，由 kafka-rust 自行选择分区
+    RoundRobin,
+    /// 手动指定分区
+    Explicit,
+    /// 按 key 做 murmur2 哈希，与真实 Kafka 客户端行为一致
+    HashByKey,
+}
+
+impl Default for PartitionMode {
+    fn default() -> Self {
+        PartitionMode::RoundRobin
+    }
+}
+
+impl PartitionMode {
+    fn label(&self) -> &'static str {
+        match self {
+            PartitionMode::RoundRobin => "轮询",
+            PartitionMode::Explicit => "指定分区",
+            PartitionMode::HashByKey => "按 key 哈希",
+        }
+    }
+}
+
+/// Kafka 默认分区器使用的 murmur2 算法（与 org.apache.kafka.clients.producer.internals.DefaultPartitioner 一致）
+fn murmur2(key: &[u8]) -> u32 {
+    const SEED: u32 = 0x9747b28c;
+    const M: u32 = 0x5bd1e995;
+    const R: u32 = 24;
+
+    let mut h: u32 = SEED ^ (key.len() as u32);
+    let chunks = key.chunks_exact(4);
+    let remainder = chunks.remainder();
+
+    for chunk in chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+
+    match remainder.len() {
+        3 => {
+            h ^= (remainder[2] as u32) << 16;
+            h ^= (remainder[1] as u32) << 8;
+            h ^= remainder[0] as u32;
+            h = h.wrapping_mul(M);
+        }
+        2 => {
+            h ^= (remainder[1] as u32) << 8;
+            h ^= remainder[0] as u32;
+            h = h.wrapping_mul(M);
+        }
+        1 => {
+            h ^= remainder[0] as u32;
+            h = h.wrapping_mul(M);
+        }
+        _ => {}
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+/// 按 Kafka 默认分区器的方式，把 key 映射到 `partition_count` 个分区中的一个
+fn partition_for_key(key: &[u8], partition_count: usize) -> i32 {
+    if partition_count == 0 {
+        return 0;
+    }
+    ((murmur2(key) & 0x7fffffff) as usize % partition_count) as i32
+}
+
+/// 在 `text` 中查找第一处与过滤条件匹配的位置；`is_regex` 为 false 时按大小写不敏感的子串匹配。
+/// 正则会在调用方按帧编译一次后以 `compiled_regex` 传入，这里不重复编译；
+/// 正则非法时 `compiled_regex` 为 `None`，此时不产生匹配（由调用方展示"正则无效"提示）
+fn find_filter_match(
+    text: &str,
+    filter: &str,
+    is_regex: bool,
+    compiled_regex: Option<&Regex>,
+) -> Option<(usize, usize)> {
+    if filter.trim().is_empty() {
+        return None;
+    }
+    if is_regex {
+        compiled_regex?.find(text).map(|m| (m.start(), m.end()))
+    } else {
+        // 用 to_ascii_lowercase 而不是 to_lowercase：后者对个别字符（如 İ）折叠后
+        // 字节长度会变化，导致算出的偏移量不再落在原始字符串的字符边界上
+        let lower_text = text.to_ascii_lowercase();
+        let lower_filter = filter.to_ascii_lowercase();
+        lower_text
+            .find(lower_filter.as_str())
+            .map(|start| (start, start + lower_filter.len()))
+    }
+}
+
+/// 把 `value` 渲染成一行，若存在匹配则高亮该片段
+fn render_value_cell(ui: &mut egui::Ui, value: &str, range: Option<(usize, usize)>) {
+    // 防御性校验：偏移量越界或不落在字符边界上时退化为不高亮，而不是 slice panic
+    let range = range.filter(|&(start, end)| {
+        end <= value.len() && value.is_char_boundary(start) && value.is_char_boundary(end)
+    });
+    match range {
+        Some((start, end)) => {
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                if start > 0 {
+                    ui.label(&value[..start]);
+                }
+                ui.label(
+                    RichText::new(&value[start..end])
+                        .background_color(Color32::from_rgb(255, 235, 59))
+                        .color(Color32::BLACK),
+                );
+                if end < value.len() {
+                    ui.label(&value[end..]);
+                }
+            });
+        }
+        None => {
+            ui.label(value);
+        }
+    }
 }
 
 #[derive(Default)]
@@ -52,9 +234,118 @@ struct CommitOffset {
 
 #[derive(Default, Debug, Clone)]
 struct KafkaMessage {
+    topic: String,
     offset: i64,
     key: String,
-    value: String,
+    value_bytes: Vec<u8>,
+}
+
+/// 值列的渲染格式；原始字节一直保留在 `KafkaMessage.value_bytes` 上，
+/// 切换格式只是换一种方式重新渲染，不需要重新拉取消息
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ValueDecoder {
+    /// 按 UTF-8 原样显示（有损）
+    Utf8,
+    /// 检测并格式化 JSON
+    Json,
+    /// 十六进制转储，适合二进制内容
+    Hex,
+}
+
+impl Default for ValueDecoder {
+    fn default() -> Self {
+        ValueDecoder::Utf8
+    }
+}
+
+impl ValueDecoder {
+    fn label(&self) -> &'static str {
+        match self {
+            ValueDecoder::Utf8 => "UTF-8",
+            ValueDecoder::Json => "JSON",
+            ValueDecoder::Hex => "十六进制",
+        }
+    }
+}
+
+/// 按选定格式把原始字节渲染成可读文本
+fn decode_value(bytes: &[u8], decoder: ValueDecoder) -> String {
+    match decoder {
+        ValueDecoder::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        ValueDecoder::Json => {
+            let text = String::from_utf8_lossy(bytes);
+            match serde_json::from_str::<serde_json::Value>(&text) {
+                Ok(value) => {
+                    serde_json::to_string_pretty(&value).unwrap_or_else(|_| text.into_owned())
+                }
+                Err(_) => text.into_owned(),
+            }
+        }
+        ValueDecoder::Hex => bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// 消费时订阅的主题范围
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SubscribeMode {
+    /// 只消费 current_topic
+    Single,
+    /// 消费多个以逗号分隔的主题
+    Multi,
+    /// 消费所有匹配正则的主题
+    Pattern,
+}
+
+impl Default for SubscribeMode {
+    fn default() -> Self {
+        SubscribeMode::Single
+    }
+}
+
+impl SubscribeMode {
+    fn label(&self) -> &'static str {
+        match self {
+            SubscribeMode::Single => "单主题",
+            SubscribeMode::Multi => "多主题",
+            SubscribeMode::Pattern => "正则匹配",
+        }
+    }
+}
+
+/// 根据订阅模式解析出实际要消费的主题列表
+fn resolve_subscribed_topics(
+    client: &mut KafkaClient,
+    current_topic: &str,
+    mode: SubscribeMode,
+    multi_input: &str,
+    pattern: &str,
+) -> Vec<String> {
+    match mode {
+        SubscribeMode::Single => vec![current_topic.to_owned()],
+        SubscribeMode::Multi => multi_input
+            .split(',')
+            .map(|s| s.trim().to_owned())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        SubscribeMode::Pattern => {
+            if client.load_metadata_all().is_err() {
+                return vec![];
+            }
+            match Regex::new(pattern) {
+                Ok(re) => client
+                    .topics()
+                    .names()
+                    .filter(|name| re.is_match(name))
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                Err(_) => vec![],
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -88,6 +379,9 @@ impl ToolApp {
         let mut app = ToolApp {
             current_offset_type: "起始".to_owned(),
             panel_id: "new".to_owned(),
+            stream_cap: 1000,
+            new_topic_partitions: 1,
+            new_topic_replication_factor: 1,
             ..ToolApp::default()
         };
         // 加载配置
@@ -128,9 +422,133 @@ impl ToolApp {
             };
         }
     }
+
+    /// 开启实时消费：后台线程持续 poll，通过 channel 把新消息送回 UI
+    pub fn start_streaming(&mut self) {
+        self.stream_error_message.clear();
+        if let Err(e) = require_non_sasl_data_plane(self.current_config.security_protocol) {
+            self.stream_error_message = e;
+            return;
+        }
+
+        let hosts: Vec<String> = self
+            .current_config
+            .host
+            .split(",")
+            .map(|h| h.to_owned())
+            .collect();
+        // 先在 UI 线程上构造一次客户端（只加载 TLS 配置，不建立连接），
+        // TLS 配置（证书路径等）有问题时及时报错，而不是让后台线程悄悄失败
+        if let Err(e) = new_kafka_client(hosts.clone(), &self.current_config) {
+            self.stream_error_message = e;
+            return;
+        }
+
+        self.stop_streaming();
+
+        let current_topic = self.current_topic.clone();
+        let config = self.current_config.clone();
+        let subscribe_mode = self.subscribe_mode;
+        let subscribe_topics_input = self.subscribe_topics_input.clone();
+        let subscribe_pattern = self.subscribe_pattern.clone();
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_clone = stop.clone();
+
+        thread::spawn(move || {
+            let mut bootstrap_client = match new_kafka_client(hosts.clone(), &config) {
+                Ok(client) => client,
+                Err(_) => return,
+            };
+            let topics = resolve_subscribed_topics(
+                &mut bootstrap_client,
+                current_topic.as_str(),
+                subscribe_mode,
+                subscribe_topics_input.as_str(),
+                subscribe_pattern.as_str(),
+            );
+
+            let mut consumers = vec![];
+            for topic in topics {
+                let mut client = match new_kafka_client(hosts.clone(), &config) {
+                    Ok(client) => client,
+                    Err(_) => continue,
+                };
+                if client.load_metadata(&vec![topic.clone()]).is_err() {
+                    continue;
+                }
+                client.set_group_offset_storage(Some(GroupOffsetStorage::Kafka));
+                let consumer = Consumer::from_client(client)
+                    .with_topic(topic.clone())
+                    .with_group(KAFKA_GROUP_ID.to_string())
+                    .with_fallback_offset(FetchOffset::Latest)
+                    .create();
+                if let Ok(consumer) = consumer {
+                    consumers.push((topic, consumer));
+                }
+            }
+
+            while !stop_clone.load(Ordering::Relaxed) {
+                if consumers.is_empty() {
+                    break;
+                }
+                for (topic, consumer) in consumers.iter_mut() {
+                    let message_sets = match consumer.poll() {
+                        Ok(ms) => ms,
+                        Err(_) => continue,
+                    };
+                    for ms in message_sets.iter() {
+                        for msg in ms.messages() {
+                            let km = KafkaMessage {
+                                topic: topic.clone(),
+                                offset: msg.offset,
+                                key: String::from_utf8_lossy(msg.key).into_owned(),
+                                value_bytes: msg.value.to_vec(),
+                            };
+                            if tx.send(km).is_err() {
+                                return;
+                            }
+                        }
+                        let _ = consumer.consume_messageset(ms);
+                    }
+                    let _ = consumer.commit_consumed();
+                }
+            }
+        });
+
+        self.stream_stop = Some(stop);
+        self.stream_rx = Some(rx);
+        self.streaming = true;
+    }
+
+    /// 停止实时消费的后台线程
+    pub fn stop_streaming(&mut self) {
+        if let Some(stop) = self.stream_stop.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        self.stream_rx = None;
+        self.streaming = false;
+    }
+
+    /// 创建/删除主题成功后，用 load_topics 同样的方式刷新主题列表
+    pub fn refresh_current_topics(&mut self) {
+        let hosts = self
+            .current_config
+            .host
+            .split(",")
+            .map(|h| h.to_string())
+            .collect();
+        load_topics(&hosts, &mut self.current_config);
+        for ele in &mut self.config.kafka_configs {
+            if ele.id == self.current_config.id {
+                ele.topics = self.current_config.topics.clone();
+            }
+        }
+        self.save_config();
+    }
 }
 
-#[derive(Default, Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 struct KafkaConfig {
     id: String,
     group_name: String,
@@ -139,6 +557,189 @@ struct KafkaConfig {
     topics: Vec<String>,
     group_ids: Vec<String>,
     message: Option<String>,
+    #[serde(default)]
+    acks: AcksMode,
+    #[serde(default = "default_ack_timeout_ms")]
+    ack_timeout_ms: u64,
+    #[serde(default)]
+    compression: CompressionMode,
+    #[serde(default)]
+    security_protocol: SecurityProtocol,
+    #[serde(default)]
+    username: String,
+    #[serde(default)]
+    ca_path: String,
+    #[serde(default)]
+    cert_path: String,
+    #[serde(default)]
+    key_path: String,
+}
+
+fn default_ack_timeout_ms() -> u64 {
+    1000
+}
+
+/// 与 Kafka `listener.security.protocol.map` 对应的安全协议选项
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+enum SecurityProtocol {
+    Plaintext,
+    Ssl,
+    SaslPlaintext,
+    SaslSsl,
+}
+
+impl Default for SecurityProtocol {
+    fn default() -> Self {
+        SecurityProtocol::Plaintext
+    }
+}
+
+impl SecurityProtocol {
+    fn label(&self) -> &'static str {
+        match self {
+            SecurityProtocol::Plaintext => "PLAINTEXT",
+            SecurityProtocol::Ssl => "SSL",
+            SecurityProtocol::SaslPlaintext => "SASL_PLAINTEXT",
+            SecurityProtocol::SaslSsl => "SASL_SSL",
+        }
+    }
+
+    fn uses_tls(&self) -> bool {
+        matches!(self, SecurityProtocol::Ssl | SecurityProtocol::SaslSsl)
+    }
+
+    fn uses_sasl(&self) -> bool {
+        matches!(
+            self,
+            SecurityProtocol::SaslPlaintext | SecurityProtocol::SaslSsl
+        )
+    }
+}
+
+/// `kafka` crate 不支持在实际收发数据的连接上叠加 SASL 认证（参见 `new_kafka_client`
+/// 的文档注释），所以消费/生产/位移类操作在配置了 SASL 时直接报错，
+/// 避免像"测试"按钮那样给出一个连不上安全集群却显示成功的假象
+fn require_non_sasl_data_plane(protocol: SecurityProtocol) -> Result<(), String> {
+    if protocol.uses_sasl() {
+        Err("SASL_PLAINTEXT/SASL_SSL 暂不支持收发消息、位移提交等数据通道操作，仅能用于连接测试和主题列表浏览；请改用 PLAINTEXT/SSL 集群。".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+const KEYRING_SERVICE: &str = "mtools-kafka";
+
+/// 密码不进配置文件，而是按 `config.id` 存进操作系统的密钥链（Keychain/Credential
+/// Manager/Secret Service），`.mtools-kafka-config` 里只留其余明文字段
+fn keyring_entry(config_id: &str) -> Result<Entry, String> {
+    Entry::new(KEYRING_SERVICE, config_id).map_err(|e| format!("访问系统密钥链失败: {}", e))
+}
+
+impl KafkaConfig {
+    fn password(&self) -> String {
+        if self.id.is_empty() {
+            return String::new();
+        }
+        keyring_entry(&self.id)
+            .and_then(|entry| entry.get_password().map_err(|e| e.to_string()))
+            .unwrap_or_default()
+    }
+
+    /// 保存密码到系统密钥链；`id` 为空时（尚未"保存"过的新配置）先分配一个
+    fn set_password(&mut self, plain: &str) -> Result<(), String> {
+        if self.id.is_empty() {
+            self.id = uuid::Uuid::new_v4().to_string();
+        }
+        keyring_entry(&self.id)?
+            .set_password(plain)
+            .map_err(|e| format!("保存密码到系统密钥链失败: {}", e))
+    }
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        KafkaConfig {
+            id: String::default(),
+            group_name: String::default(),
+            name: String::default(),
+            host: String::default(),
+            topics: Vec::default(),
+            group_ids: Vec::default(),
+            message: None,
+            acks: AcksMode::default(),
+            ack_timeout_ms: default_ack_timeout_ms(),
+            compression: CompressionMode::default(),
+            security_protocol: SecurityProtocol::default(),
+            username: String::default(),
+            ca_path: String::default(),
+            cert_path: String::default(),
+            key_path: String::default(),
+        }
+    }
+}
+
+/// 生产者的 acks 语义，对应 `kafka::producer::RequiredAcks`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+enum AcksMode {
+    None,
+    One,
+    All,
+}
+
+impl Default for AcksMode {
+    fn default() -> Self {
+        AcksMode::One
+    }
+}
+
+impl AcksMode {
+    fn label(&self) -> &'static str {
+        match self {
+            AcksMode::None => "None",
+            AcksMode::One => "One",
+            AcksMode::All => "All",
+        }
+    }
+
+    fn to_required_acks(self) -> RequiredAcks {
+        match self {
+            AcksMode::None => RequiredAcks::None,
+            AcksMode::One => RequiredAcks::One,
+            AcksMode::All => RequiredAcks::All,
+        }
+    }
+}
+
+/// 生产者压缩编码，对应 `kafka::producer::Compression`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+enum CompressionMode {
+    None,
+    Gzip,
+    Snappy,
+}
+
+impl Default for CompressionMode {
+    fn default() -> Self {
+        CompressionMode::None
+    }
+}
+
+impl CompressionMode {
+    fn label(&self) -> &'static str {
+        match self {
+            CompressionMode::None => "none",
+            CompressionMode::Gzip => "gzip",
+            CompressionMode::Snappy => "snappy",
+        }
+    }
+
+    fn to_compression(self) -> Compression {
+        match self {
+            CompressionMode::None => Compression::NONE,
+            CompressionMode::Gzip => Compression::GZIP,
+            CompressionMode::Snappy => Compression::SNAPPY,
+        }
+    }
 }
 
 impl eframe::App for ToolApp {
@@ -236,6 +837,12 @@ impl eframe::App for ToolApp {
                             if ui.button("修改偏移量").clicked() {
                                 self.list_panel_id = Some("commit_offset".to_owned());
                             }
+                            if ui.button("消费进度").clicked() {
+                                self.list_panel_id = Some("lag".to_owned());
+                            }
+                            if ui.button("主题管理").clicked() {
+                                self.list_panel_id = Some("topic_admin".to_owned());
+                            }
                         });
                     });
                     ui.horizontal(|_ui| match &self.list_panel_id {
@@ -248,45 +855,76 @@ impl eframe::App for ToolApp {
                                         .split(",")
                                         .map(|h| h.to_owned())
                                         .collect();
-                                    let mut client = KafkaClient::new(hosts);
-                                    client
-                                        .set_group_offset_storage(Some(GroupOffsetStorage::Kafka));
-                                    match client.load_metadata(&vec![self.current_topic.clone()]) {
-                                        Ok(_) => {
-                                            self.partition_offsets.clear();
-
-                                            let topic_partition_offset = client
-                                                .fetch_offsets(
-                                                    &vec![self.current_topic.clone()],
-                                                    FetchOffset::Earliest,
-                                                )
-                                                .unwrap();
-                                            let partition_offsets = topic_partition_offset
-                                                .get(self.current_topic.as_str())
-                                                .unwrap();
-                                            for po in partition_offsets {
-                                                self.commit_offset.start_offset = po.offset;
-                                            }
+                                    let mut client_opt = match require_non_sasl_data_plane(
+                                        self.current_config.security_protocol,
+                                    )
+                                    .and_then(|_| new_kafka_client(hosts, &self.current_config))
+                                    {
+                                        Ok(mut client) => {
+                                            client.set_group_offset_storage(Some(
+                                                GroupOffsetStorage::Kafka,
+                                            ));
+                                            match client
+                                                .load_metadata(&vec![self.current_topic.clone()])
+                                            {
+                                                Ok(_) => {
+                                                    self.partition_offsets.clear();
 
-                                            let topic_partition_offset = client
-                                                .fetch_offsets(
-                                                    &vec![self.current_topic.clone()],
-                                                    FetchOffset::Latest,
-                                                )
-                                                .unwrap();
-                                            let partition_offsets = topic_partition_offset
-                                                .get(self.current_topic.as_str())
-                                                .unwrap();
-                                            for po in partition_offsets {
-                                                self.commit_offset.start_offset = po.offset;
-                                                self.partition_offsets.push(PartitionOffset {
-                                                    offset: po.offset,
-                                                    partition: po.partition,
-                                                });
+                                                    match client.fetch_offsets(
+                                                        &vec![self.current_topic.clone()],
+                                                        FetchOffset::Earliest,
+                                                    ) {
+                                                        Ok(m) => {
+                                                            if let Some(partition_offsets) =
+                                                                m.get(self.current_topic.as_str())
+                                                            {
+                                                                for po in partition_offsets {
+                                                                    self.commit_offset
+                                                                        .start_offset = po.offset;
+                                                                }
+                                                            }
+                                                        }
+                                                        Err(e) => {
+                                                            self.commit_offset.error_message =
+                                                                e.to_string();
+                                                        }
+                                                    }
+
+                                                    match client.fetch_offsets(
+                                                        &vec![self.current_topic.clone()],
+                                                        FetchOffset::Latest,
+                                                    ) {
+                                                        Ok(m) => {
+                                                            if let Some(partition_offsets) =
+                                                                m.get(self.current_topic.as_str())
+                                                            {
+                                                                for po in partition_offsets {
+                                                                    self.commit_offset.end_offset =
+                                                                        po.offset;
+                                                                    self.partition_offsets.push(
+                                                                        PartitionOffset {
+                                                                            offset: po.offset,
+                                                                            partition: po.partition,
+                                                                        },
+                                                                    );
+                                                                }
+                                                            }
+                                                        }
+                                                        Err(e) => {
+                                                            self.commit_offset.error_message =
+                                                                e.to_string();
+                                                        }
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    self.commit_offset.error_message = e.to_string();
+                                                }
                                             }
+                                            Some(client)
                                         }
                                         Err(e) => {
-                                            println!("{}", e);
+                                            self.commit_offset.error_message = e;
+                                            None
                                         }
                                     };
 
@@ -344,7 +982,7 @@ impl eframe::App for ToolApp {
                                             {
                                                 self.commit_offset.error_message =
                                                     "偏移量大于最大偏移量了".to_owned();
-                                            } else {
+                                            } else if let Some(client) = client_opt.as_mut() {
                                                 for po in &self.partition_offsets {
                                                     match client.commit_offset(
                                                         self.commit_offset.commit_group.as_str(),
@@ -364,44 +1002,501 @@ impl eframe::App for ToolApp {
                                     });
                                 });
                             }
+                            "lag" => {
+                                egui::CentralPanel::default().show(ctx, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label("消费组 ID:");
+                                        ui.text_edit_singleline(&mut self.lag_group);
+                                        if ui.button("刷新").clicked() {
+                                            self.lag_error_message = "".to_owned();
+                                            let hosts = self
+                                                .current_config
+                                                .host
+                                                .split(",")
+                                                .map(|h| h.to_owned())
+                                                .collect();
+                                            match require_non_sasl_data_plane(
+                                                self.current_config.security_protocol,
+                                            )
+                                            .and_then(|_| {
+                                                new_kafka_client(hosts, &self.current_config)
+                                            }) {
+                                                Err(e) => {
+                                                    self.lag_error_message = e;
+                                                }
+                                                Ok(mut client) => {
+                                            client.set_group_offset_storage(Some(
+                                                GroupOffsetStorage::Kafka,
+                                            ));
+                                            match client
+                                                .load_metadata(&vec![self.current_topic.clone()])
+                                            {
+                                                Ok(_) => {
+                                                    let earliest = client.fetch_offsets(
+                                                        &vec![self.current_topic.clone()],
+                                                        FetchOffset::Earliest,
+                                                    );
+                                                    let latest = client.fetch_offsets(
+                                                        &vec![self.current_topic.clone()],
+                                                        FetchOffset::Latest,
+                                                    );
+                                                    match (earliest, latest) {
+                                                        (Ok(earliest), Ok(latest)) => {
+                                                            let committed = client
+                                                                .fetch_group_offsets(
+                                                                    self.lag_group.as_str(),
+                                                                    self.current_topic.as_str(),
+                                                                )
+                                                                .unwrap_or_default();
+
+                                                            let earliest = earliest
+                                                                .get(self.current_topic.as_str());
+                                                            let latest = latest
+                                                                .get(self.current_topic.as_str());
+
+                                                            let mut lags = vec![];
+                                                            if let (Some(earliest), Some(latest)) =
+                                                                (earliest, latest)
+                                                            {
+                                                                for lo in earliest {
+                                                                    let hi = latest
+                                                                        .iter()
+                                                                        .find(|p| {
+                                                                            p.partition
+                                                                                == lo.partition
+                                                                        })
+                                                                        .map(|p| p.offset)
+                                                                        .unwrap_or(lo.offset);
+                                                                    // 从未提交过的分区 committed 字段按 0 展示，
+                                                                    // 与下面 lag 的计算口径保持一致
+                                                                    let committed_offset = committed
+                                                                        .iter()
+                                                                        .find(|p| {
+                                                                            p.partition
+                                                                                == lo.partition
+                                                                        })
+                                                                        .map(|p| p.offset)
+                                                                        .unwrap_or(-1)
+                                                                        .max(0);
+                                                                    lags.push(PartitionLag {
+                                                                        partition: lo.partition,
+                                                                        earliest: lo.offset,
+                                                                        latest: hi,
+                                                                        committed: committed_offset,
+                                                                        lag: hi - committed_offset,
+                                                                    });
+                                                                }
+                                                            }
+                                                            self.partition_lags = lags;
+                                                        }
+                                                        (Err(e), _) | (_, Err(e)) => {
+                                                            self.lag_error_message = e.to_string();
+                                                        }
+                                                    }
+                                                }
+                                                Err(e) => {
+                                                    self.lag_error_message = e.to_string();
+                                                }
+                                            };
+                                                }
+                                            }
+                                        }
+                                    });
+                                    if !self.lag_error_message.is_empty() {
+                                        ui.label(
+                                            RichText::new(self.lag_error_message.as_str())
+                                                .color(Color32::from_rgb(255, 0, 0)),
+                                        );
+                                    }
+                                    TableBuilder::new(ui)
+                                        .striped(true)
+                                        .resizable(true)
+                                        .cell_layout(Layout::left_to_right(Align::Center))
+                                        .column(Column::auto())
+                                        .column(Column::auto())
+                                        .column(Column::auto())
+                                        .column(Column::auto())
+                                        .column(Column::remainder())
+                                        .header(20.0, |mut header| {
+                                            header.col(|ui| {
+                                                ui.label("分区");
+                                            });
+                                            header.col(|ui| {
+                                                ui.label("起始偏移量");
+                                            });
+                                            header.col(|ui| {
+                                                ui.label("最新偏移量");
+                                            });
+                                            header.col(|ui| {
+                                                ui.label("已提交偏移量");
+                                            });
+                                            header.col(|ui| {
+                                                ui.label("滞后 (lag)");
+                                            });
+                                        })
+                                        .body(|mut body| {
+                                            for pl in &self.partition_lags {
+                                                body.row(24.0, |mut row| {
+                                                    row.col(|ui| {
+                                                        ui.label(pl.partition.to_string());
+                                                    });
+                                                    row.col(|ui| {
+                                                        ui.label(pl.earliest.to_string());
+                                                    });
+                                                    row.col(|ui| {
+                                                        ui.label(pl.latest.to_string());
+                                                    });
+                                                    row.col(|ui| {
+                                                        // 点击已提交偏移量，将其填入"修改偏移量"面板，方便直接调整
+                                                        if ui
+                                                            .add(
+                                                                Label::new(pl.committed.to_string())
+                                                                    .sense(Sense::click()),
+                                                            )
+                                                            .clicked()
+                                                        {
+                                                            self.commit_offset.commit_offset =
+                                                                pl.committed;
+                                                            self.commit_offset.commit_group =
+                                                                self.lag_group.clone();
+                                                        }
+                                                    });
+                                                    row.col(|ui| {
+                                                        let color = if pl.lag > 1000 {
+                                                            Color32::from_rgb(255, 0, 0)
+                                                        } else if pl.lag > 0 {
+                                                            Color32::from_rgb(230, 160, 0)
+                                                        } else {
+                                                            ui.visuals().text_color()
+                                                        };
+                                                        ui.label(
+                                                            RichText::new(pl.lag.to_string())
+                                                                .color(color),
+                                                        );
+                                                    });
+                                                });
+                                            }
+                                        });
+                                });
+                            }
+                            "topic_admin" => {
+                                egui::CentralPanel::default().show(ctx, |ui| {
+                                    ui.heading("创建主题");
+                                    egui::Grid::new("new_topic_grid").show(ui, |ui| {
+                                        ui.label("主题名称");
+                                        ui.text_edit_singleline(&mut self.new_topic_name);
+                                        ui.end_row();
+
+                                        ui.label("分区数");
+                                        ui.add(egui::DragValue::new(
+                                            &mut self.new_topic_partitions,
+                                        ));
+                                        ui.end_row();
+
+                                        ui.label("副本因子");
+                                        ui.add(egui::DragValue::new(
+                                            &mut self.new_topic_replication_factor,
+                                        ));
+                                        ui.end_row();
+                                    });
+
+                                    ui.label("主题配置 (如 retention.ms / cleanup.policy)");
+                                    let mut remove_index = None;
+                                    for (index, (key, value)) in
+                                        self.new_topic_configs.iter_mut().enumerate()
+                                    {
+                                        ui.horizontal(|ui| {
+                                            ui.text_edit_singleline(key);
+                                            ui.label("=");
+                                            ui.text_edit_singleline(value);
+                                            if ui.button("删除").clicked() {
+                                                remove_index = Some(index);
+                                            }
+                                        });
+                                    }
+                                    if let Some(index) = remove_index {
+                                        self.new_topic_configs.remove(index);
+                                    }
+                                    if ui.button("+ 添加配置项").clicked() {
+                                        self.new_topic_configs
+                                            .push((String::new(), String::new()));
+                                    }
+
+                                    if ui.button("创建").clicked() {
+                                        if self.current_config.security_protocol
+                                            != SecurityProtocol::Plaintext
+                                        {
+                                            self.topic_admin_message =
+                                                "主题管理暂只支持 PLAINTEXT 明文连接，当前协议的集群请改用命令行工具操作"
+                                                    .to_owned();
+                                        } else {
+                                        let hosts: Vec<String> = self
+                                            .current_config
+                                            .host
+                                            .split(",")
+                                            .map(|h| h.to_owned())
+                                            .collect();
+                                        let config_map: HashMap<String, String> = self
+                                            .new_topic_configs
+                                            .iter()
+                                            .filter(|(k, _)| !k.trim().is_empty())
+                                            .cloned()
+                                            .collect();
+                                        let topic = admin::CreatableTopic {
+                                            name: self.new_topic_name.clone(),
+                                            num_partitions: self.new_topic_partitions,
+                                            replication_factor: self.new_topic_replication_factor,
+                                            config_map,
+                                        };
+                                        match admin::create_topics(&hosts, &[topic], 5000) {
+                                            Ok(results) => {
+                                                match results.first() {
+                                                    Some(r) if r.is_ok() => {
+                                                        self.topic_admin_message =
+                                                            "创建成功!".to_owned();
+                                                        self.refresh_current_topics();
+                                                    }
+                                                    Some(r) => {
+                                                        self.topic_admin_message = format!(
+                                                            "创建失败, error_code={}",
+                                                            r.error_code
+                                                        );
+                                                    }
+                                                    None => {
+                                                        self.topic_admin_message =
+                                                            "broker 未返回结果".to_owned();
+                                                    }
+                                                }
+                                            }
+                                            Err(e) => {
+                                                self.topic_admin_message =
+                                                    format!("创建失败: {}", e);
+                                            }
+                                        }
+                                        }
+                                    }
+
+                                    ui.separator();
+                                    ui.heading("删除主题");
+                                    ui.horizontal(|ui| {
+                                        ui.label("主题名称");
+                                        ui.text_edit_singleline(&mut self.delete_topic_name);
+                                        if ui.button("删除").clicked() {
+                                            if self.current_config.security_protocol
+                                                != SecurityProtocol::Plaintext
+                                            {
+                                                self.topic_admin_message =
+                                                    "主题管理暂只支持 PLAINTEXT 明文连接，当前协议的集群请改用命令行工具操作"
+                                                        .to_owned();
+                                            } else {
+                                            let hosts: Vec<String> = self
+                                                .current_config
+                                                .host
+                                                .split(",")
+                                                .map(|h| h.to_owned())
+                                                .collect();
+                                            match admin::delete_topics(
+                                                &hosts,
+                                                &[self.delete_topic_name.clone()],
+                                                5000,
+                                            ) {
+                                                Ok(results) => match results.first() {
+                                                    Some(r) if r.is_ok() => {
+                                                        self.topic_admin_message =
+                                                            "删除成功!".to_owned();
+                                                        self.refresh_current_topics();
+                                                    }
+                                                    Some(r) => {
+                                                        self.topic_admin_message = format!(
+                                                            "删除失败, error_code={}",
+                                                            r.error_code
+                                                        );
+                                                    }
+                                                    None => {
+                                                        self.topic_admin_message =
+                                                            "broker 未返回结果".to_owned();
+                                                    }
+                                                },
+                                                Err(e) => {
+                                                    self.topic_admin_message =
+                                                        format!("删除失败: {}", e);
+                                                }
+                                            }
+                                            }
+                                        }
+                                    });
+
+                                    if !self.topic_admin_message.is_empty() {
+                                        ui.label(
+                                            RichText::new(self.topic_admin_message.as_str())
+                                                .color(Color32::from_rgb(255, 0, 0)),
+                                        );
+                                    }
+                                });
+                            }
                             "send_data" => {
                                 egui::CentralPanel::default().show(ctx, |ui| {
                                     ui.with_layout(Layout::bottom_up(Align::Max), |ui| {
                                         ui.horizontal(|ui| {
                                             if ui.button("确认").clicked() {
-                                                let producer = if self.kafka_producer.is_none() {
-                                                    let hosts = self
-                                                        .current_config
-                                                        .host
-                                                        .clone()
-                                                        .split(",")
-                                                        .map(|h| h.to_string())
-                                                        .collect();
-                                                    self.kafka_producer = Some(
-                                                        Producer::from_hosts(hosts)
-                                                            .with_ack_timeout(Duration::from_secs(
-                                                                1,
-                                                            ))
-                                                            .with_required_acks(RequiredAcks::One)
-                                                            .create()
-                                                            .unwrap(),
-                                                    );
-                                                    self.kafka_producer.as_mut()
+                                                if let Err(e) = require_non_sasl_data_plane(
+                                                    self.current_config.security_protocol,
+                                                ) {
+                                                    self.send_message = e;
                                                 } else {
-                                                    self.kafka_producer.as_mut()
-                                                }
-                                                .unwrap();
-                                                match producer.send(&Record::from_value(
-                                                    self.current_topic.as_str(),
-                                                    self.send_value.as_bytes(),
-                                                )) {
-                                                    Ok(_) => {
-                                                        self.send_message = "".to_owned();
+                                                let hosts: Vec<String> = self
+                                                    .current_config
+                                                    .host
+                                                    .clone()
+                                                    .split(",")
+                                                    .map(|h| h.to_string())
+                                                    .collect();
+
+                                                // 按 key 哈希时需要先拿到分区数量
+                                                let mut partition_resolution_failed = false;
+                                                let partition = match self.send_partition_mode {
+                                                    PartitionMode::Explicit => {
+                                                        Some(self.send_partition)
                                                     }
-                                                    Err(e) => {
-                                                        self.send_message = e.to_string();
+                                                    // 空 key 没有哈希依据，退化为轮询（None 分区、发送时丢弃 key）
+                                                    PartitionMode::HashByKey
+                                                        if self.send_key.is_empty() =>
+                                                    {
+                                                        None
+                                                    }
+                                                    PartitionMode::HashByKey => {
+                                                        match new_kafka_client(
+                                                            hosts.clone(),
+                                                            &self.current_config,
+                                                        ) {
+                                                            Ok(mut client) => match client
+                                                                .load_metadata(&vec![
+                                                                    self.current_topic.clone(),
+                                                                ]) {
+                                                                Ok(_) => {
+                                                                    let partition_count = client
+                                                                        .topics()
+                                                                        .partitions(
+                                                                            self.current_topic
+                                                                                .as_str(),
+                                                                        )
+                                                                        .map(|p| p.len())
+                                                                        .unwrap_or(0);
+                                                                    Some(partition_for_key(
+                                                                        self.send_key.as_bytes(),
+                                                                        partition_count,
+                                                                    ))
+                                                                }
+                                                                Err(e) => {
+                                                                    self.send_message =
+                                                                        e.to_string();
+                                                                    partition_resolution_failed =
+                                                                        true;
+                                                                    None
+                                                                }
+                                                            },
+                                                            Err(e) => {
+                                                                self.send_message = e;
+                                                                partition_resolution_failed = true;
+                                                                None
+                                                            }
+                                                        }
+                                                    }
+                                                    PartitionMode::RoundRobin => None,
+                                                };
+
+                                                if !partition_resolution_failed {
+                                                    let signature = (
+                                                        self.current_config.acks,
+                                                        self.current_config.ack_timeout_ms,
+                                                        self.current_config.compression,
+                                                    );
+                                                    let mut producer_error = None;
+                                                    if self.kafka_producer.is_none()
+                                                        || self.kafka_producer_signature
+                                                            != Some(signature)
+                                                    {
+                                                        match new_kafka_client(
+                                                            hosts,
+                                                            &self.current_config,
+                                                        ) {
+                                                            Ok(client) => {
+                                                                self.kafka_producer = Some(
+                                                                    Producer::from_client(client)
+                                                                        .with_ack_timeout(
+                                                                            Duration::from_millis(
+                                                                                self.current_config
+                                                                                    .ack_timeout_ms,
+                                                                            ),
+                                                                        )
+                                                                        .with_required_acks(
+                                                                            self.current_config
+                                                                                .acks
+                                                                                .to_required_acks(),
+                                                                        )
+                                                                        .with_compression(
+                                                                            self.current_config
+                                                                                .compression
+                                                                                .to_compression(),
+                                                                        )
+                                                                        .create()
+                                                                        .unwrap(),
+                                                                );
+                                                                self.kafka_producer_signature =
+                                                                    Some(signature);
+                                                            }
+                                                            Err(e) => {
+                                                                producer_error = Some(e);
+                                                            }
+                                                        }
+                                                    }
+
+                                                    if let Some(e) = producer_error {
+                                                        self.send_message = e;
+                                                    } else {
+                                                    let producer =
+                                                        self.kafka_producer.as_mut().unwrap();
+
+                                                    // 批量模式下，文本框里每一行作为一条独立消息发送，
+                                                    // 与 python-kafka KeyedProducer.send_messages 的多消息语义对应
+                                                    let values: Vec<&str> = if self.send_batch_by_line
+                                                    {
+                                                        self.send_value
+                                                            .lines()
+                                                            .filter(|l| !l.trim().is_empty())
+                                                            .collect()
+                                                    } else {
+                                                        vec![self.send_value.as_str()]
+                                                    };
+
+                                                    let mut error = None;
+                                                    for value in values {
+                                                        let send_result = match partition {
+                                                            Some(p) => producer.send(
+                                                                &Record::from_key_value(
+                                                                    self.current_topic.as_str(),
+                                                                    self.send_key.as_bytes(),
+                                                                    value.as_bytes(),
+                                                                )
+                                                                .with_partition(p),
+                                                            ),
+                                                            // 轮询模式下分区号始终为 None；kafka-rust 在带 key
+                                                            // 发送时会按 key 哈希选择分区而不是真正轮询，
+                                                            // 所以这里始终丢弃 key，让 producer 自行轮询分区
+                                                            None => producer.send(&Record::from_value(
+                                                                self.current_topic.as_str(),
+                                                                value.as_bytes(),
+                                                            )),
+                                                        };
+                                                        if let Err(e) = send_result {
+                                                            error = Some(e.to_string());
+                                                            break;
+                                                        }
+                                                    }
+                                                    self.send_message = error.unwrap_or_default();
                                                     }
                                                 }
+                                                }
                                             }
                                             if self.send_message != "" {
                                                 ui.label(
@@ -410,6 +1505,74 @@ impl eframe::App for ToolApp {
                                                 );
                                             }
                                         });
+                                        ui.horizontal(|ui| {
+                                            ui.label("分区策略:");
+                                            egui::ComboBox::from_id_source("send_partition_mode")
+                                                .selected_text(self.send_partition_mode.label())
+                                                .show_ui(ui, |ui| {
+                                                    for mode in [
+                                                        PartitionMode::RoundRobin,
+                                                        PartitionMode::Explicit,
+                                                        PartitionMode::HashByKey,
+                                                    ] {
+                                                        ui.selectable_value(
+                                                            &mut self.send_partition_mode,
+                                                            mode,
+                                                            mode.label(),
+                                                        );
+                                                    }
+                                                });
+                                            if self.send_partition_mode == PartitionMode::Explicit
+                                            {
+                                                ui.label("分区号:");
+                                                ui.add(egui::DragValue::new(
+                                                    &mut self.send_partition,
+                                                ));
+                                            }
+                                        });
+                                        ui.horizontal(|ui| {
+                                            ui.label("键:");
+                                            ui.text_edit_singleline(&mut self.send_key);
+                                            ui.checkbox(&mut self.send_batch_by_line, "按行批量发送");
+                                        });
+                                        ui.horizontal(|ui| {
+                                            ui.label("确认机制 (acks):");
+                                            egui::ComboBox::from_id_source("send_acks_mode")
+                                                .selected_text(self.current_config.acks.label())
+                                                .show_ui(ui, |ui| {
+                                                    for mode in
+                                                        [AcksMode::None, AcksMode::One, AcksMode::All]
+                                                    {
+                                                        ui.selectable_value(
+                                                            &mut self.current_config.acks,
+                                                            mode,
+                                                            mode.label(),
+                                                        );
+                                                    }
+                                                });
+                                            ui.label("超时(ms):");
+                                            ui.add(egui::DragValue::new(
+                                                &mut self.current_config.ack_timeout_ms,
+                                            ));
+                                            ui.label("压缩:");
+                                            egui::ComboBox::from_id_source("send_compression_mode")
+                                                .selected_text(
+                                                    self.current_config.compression.label(),
+                                                )
+                                                .show_ui(ui, |ui| {
+                                                    for mode in [
+                                                        CompressionMode::None,
+                                                        CompressionMode::Gzip,
+                                                        CompressionMode::Snappy,
+                                                    ] {
+                                                        ui.selectable_value(
+                                                            &mut self.current_config.compression,
+                                                            mode,
+                                                            mode.label(),
+                                                        );
+                                                    }
+                                                });
+                                        });
                                         ui.centered_and_justified(|ui| {
                                             ui.text_edit_multiline(&mut self.send_value);
                                         });
@@ -438,87 +1601,181 @@ impl eframe::App for ToolApp {
                                         ui.label("  ");
                                         ui.label("拉取数量:");
                                         ui.add(egui::DragValue::new(&mut self.poll_rows));
-                                        if ui.button("拉取").clicked() {
-                                            let hosts = self
+
+                                        ui.label("  ");
+                                        ui.label("订阅:");
+                                        egui::ComboBox::from_id_source("subscribe_mode")
+                                            .selected_text(self.subscribe_mode.label())
+                                            .show_ui(ui, |ui| {
+                                                for mode in [
+                                                    SubscribeMode::Single,
+                                                    SubscribeMode::Multi,
+                                                    SubscribeMode::Pattern,
+                                                ] {
+                                                    ui.selectable_value(
+                                                        &mut self.subscribe_mode,
+                                                        mode,
+                                                        mode.label(),
+                                                    );
+                                                }
+                                            });
+                                        match self.subscribe_mode {
+                                            SubscribeMode::Multi => {
+                                                ui.text_edit_singleline(
+                                                    &mut self.subscribe_topics_input,
+                                                );
+                                            }
+                                            SubscribeMode::Pattern => {
+                                                ui.text_edit_singleline(&mut self.subscribe_pattern);
+                                            }
+                                            SubscribeMode::Single => {}
+                                        }
+
+                                        ui.label("  ");
+                                        let mut streaming = self.streaming;
+                                        if ui.checkbox(&mut streaming, "实时").changed() {
+                                            if streaming {
+                                                self.start_streaming();
+                                            } else {
+                                                self.stop_streaming();
+                                            }
+                                        }
+
+                                        if self.streaming {
+                                            if let Some(rx) = &self.stream_rx {
+                                                while let Ok(msg) = rx.try_recv() {
+                                                    self.current_messages.push(msg);
+                                                }
+                                                let len = self.current_messages.len();
+                                                if len > self.stream_cap {
+                                                    self.current_messages
+                                                        .drain(0..len - self.stream_cap);
+                                                }
+                                            }
+                                            ctx.request_repaint();
+                                        }
+
+                                        if ui
+                                            .add_enabled(!self.streaming, egui::Button::new("拉取"))
+                                            .clicked()
+                                        {
+                                            self.stream_error_message.clear();
+                                            if let Err(e) = require_non_sasl_data_plane(
+                                                self.current_config.security_protocol,
+                                            ) {
+                                                self.stream_error_message = e;
+                                            } else {
+                                            let hosts: Vec<String> = self
                                                 .current_config
                                                 .host
                                                 .split(",")
                                                 .map(|h| h.to_owned())
                                                 .collect();
-                                            self.partition_offsets.clear();
 
-                                            let mut client = KafkaClient::new(hosts);
-                                            client.set_group_offset_storage(Some(
-                                                GroupOffsetStorage::Kafka,
-                                            ));
-                                            client
-                                                .load_metadata(&vec![self.current_topic.clone()])
-                                                .unwrap();
-
-                                            match self.current_offset_type.as_str() {
-                                                "起始" => {
-                                                    let topic_partition_offset = client
-                                                        .fetch_offsets(
-                                                            &vec![self.current_topic.clone()],
-                                                            FetchOffset::Earliest,
-                                                        )
-                                                        .unwrap();
-                                                    let partition_offsets = topic_partition_offset
-                                                        .get(self.current_topic.as_str())
-                                                        .unwrap();
-                                                    for po in partition_offsets {
-                                                        client
-                                                            .commit_offset(
+                                            match new_kafka_client(hosts.clone(), &self.current_config)
+                                            {
+                                                Ok(mut bootstrap_client) => {
+                                            let topics = resolve_subscribed_topics(
+                                                &mut bootstrap_client,
+                                                self.current_topic.as_str(),
+                                                self.subscribe_mode,
+                                                self.subscribe_topics_input.as_str(),
+                                                self.subscribe_pattern.as_str(),
+                                            );
+
+                                            let mut messages = vec![];
+                                            for topic in &topics {
+                                                if messages.len() >= self.poll_rows {
+                                                    break;
+                                                }
+                                                self.partition_offsets.clear();
+
+                                                let mut client = match new_kafka_client(
+                                                    hosts.clone(),
+                                                    &self.current_config,
+                                                ) {
+                                                    Ok(client) => client,
+                                                    Err(_) => continue,
+                                                };
+                                                client.set_group_offset_storage(Some(
+                                                    GroupOffsetStorage::Kafka,
+                                                ));
+                                                if client.load_metadata(&vec![topic.clone()]).is_err()
+                                                {
+                                                    continue;
+                                                }
+
+                                                match self.current_offset_type.as_str() {
+                                                    "起始" => {
+                                                        let topic_partition_offset =
+                                                            match client.fetch_offsets(
+                                                                &vec![topic.clone()],
+                                                                FetchOffset::Earliest,
+                                                            ) {
+                                                                Ok(m) => m,
+                                                                Err(_) => continue,
+                                                            };
+                                                        let partition_offsets =
+                                                            match topic_partition_offset
+                                                                .get(topic.as_str())
+                                                            {
+                                                                Some(p) => p,
+                                                                None => continue,
+                                                            };
+                                                        for po in partition_offsets {
+                                                            let _ = client.commit_offset(
                                                                 KAFKA_GROUP_ID,
-                                                                self.current_topic.as_str(),
+                                                                topic.as_str(),
                                                                 po.partition,
                                                                 po.offset,
-                                                            )
-                                                            .unwrap();
+                                                            );
+                                                        }
                                                     }
-                                                }
-                                                _ => {
-                                                    let topic_partition_offset = client
-                                                        .fetch_offsets(
-                                                            &vec![self.current_topic.clone()],
-                                                            FetchOffset::Latest,
-                                                        )
-                                                        .unwrap();
-                                                    let partition_offsets = topic_partition_offset
-                                                        .get(self.current_topic.as_str())
-                                                        .unwrap();
+                                                    _ => {
+                                                        let topic_partition_offset =
+                                                            match client.fetch_offsets(
+                                                                &vec![topic.clone()],
+                                                                FetchOffset::Latest,
+                                                            ) {
+                                                                Ok(m) => m,
+                                                                Err(_) => continue,
+                                                            };
+                                                        let partition_offsets =
+                                                            match topic_partition_offset
+                                                                .get(topic.as_str())
+                                                            {
+                                                                Some(p) => p,
+                                                                None => continue,
+                                                            };
 
-                                                    let sub_count: i64 =
-                                                        if partition_offsets.len() > 0 {
+                                                        let sub_count: i64 = if partition_offsets
+                                                            .len()
+                                                            > 0
+                                                        {
                                                             self.poll_rows / partition_offsets.len()
                                                         } else {
                                                             self.poll_rows
                                                         }
                                                             as i64;
 
-                                                    for po in partition_offsets {
-                                                        let mut offset = po.offset - sub_count;
-                                                        if offset < 0 {
-                                                            offset = 0;
-                                                        }
-                                                        client
-                                                            .commit_offset(
+                                                        for po in partition_offsets {
+                                                            let mut offset = po.offset - sub_count;
+                                                            if offset < 0 {
+                                                                offset = 0;
+                                                            }
+                                                            let _ = client.commit_offset(
                                                                 KAFKA_GROUP_ID,
-                                                                self.current_topic.as_str(),
+                                                                topic.as_str(),
                                                                 po.partition,
                                                                 offset,
-                                                            )
-                                                            .unwrap();
+                                                            );
+                                                        }
                                                     }
-                                                }
-                                            };
-                                            let mut messages = vec![];
-                                            let mut reqs = vec![];
-                                            for po in &self.partition_offsets {
-                                                reqs.push((po.partition, po.offset))
-                                            }
-                                            let mut consumer = Consumer::from_client(client)
-                                                .with_topic(self.current_topic.clone())
+                                                };
+                                                let mut consumer = match Consumer::from_client(
+                                                    client,
+                                                )
+                                                .with_topic(topic.clone())
                                                 .with_group(KAFKA_GROUP_ID.to_string())
                                                 .with_fallback_offset(
                                                     match self.current_offset_type.as_str() {
@@ -527,41 +1784,246 @@ impl eframe::App for ToolApp {
                                                     },
                                                 )
                                                 .create()
-                                                .unwrap();
-                                            loop {
-                                                if messages.len() >= self.poll_rows {
-                                                    break;
-                                                }
-                                                let ms = consumer.poll().unwrap();
-                                                if ms.is_empty() {
-                                                    break;
-                                                }
+                                                {
+                                                    Ok(consumer) => consumer,
+                                                    Err(_) => continue,
+                                                };
+                                                loop {
+                                                    if messages.len() >= self.poll_rows {
+                                                        break;
+                                                    }
+                                                    let ms = match consumer.poll() {
+                                                        Ok(ms) => ms,
+                                                        Err(_) => break,
+                                                    };
+                                                    if ms.is_empty() {
+                                                        break;
+                                                    }
 
-                                                for ms in ms.iter() {
-                                                    for msg in ms.messages() {
-                                                        messages.push(KafkaMessage {
-                                                            offset: msg.offset.to_owned(),
-                                                            key: String::from_utf8_lossy(&msg.key)
-                                                                .parse()
-                                                                .unwrap(),
-                                                            value: String::from_utf8_lossy(
-                                                                &msg.value,
-                                                            )
-                                                            .parse()
-                                                            .unwrap(),
-                                                        });
+                                                    for ms in ms.iter() {
+                                                        for msg in ms.messages() {
+                                                            messages.push(KafkaMessage {
+                                                                topic: topic.clone(),
+                                                                offset: msg.offset.to_owned(),
+                                                                key: String::from_utf8_lossy(
+                                                                    &msg.key,
+                                                                )
+                                                                .into_owned(),
+                                                                value_bytes: msg.value.to_vec(),
+                                                            });
+                                                            if messages.len() >= self.poll_rows {
+                                                                break;
+                                                            }
+                                                        }
+                                                        if consumer.consume_messageset(ms).is_err()
+                                                        {
+                                                            continue;
+                                                        }
                                                         if messages.len() >= self.poll_rows {
+                                                            let _ = consumer.commit_consumed();
                                                             break;
                                                         }
                                                     }
-                                                    consumer.consume_messageset(ms).unwrap();
-                                                    if messages.len() >= self.poll_rows {
-                                                        consumer.commit_consumed().unwrap();
-                                                        break;
-                                                    }
                                                 }
                                             }
                                             self.current_messages = messages;
+                                                }
+                                                Err(e) => {
+                                                    self.stream_error_message = e;
+                                                }
+                                            }
+                                            }
+                                        }
+                                    });
+
+                                    if !self.stream_error_message.is_empty() {
+                                        ui.label(
+                                            RichText::new(self.stream_error_message.as_str())
+                                                .color(Color32::from_rgb(255, 0, 0)),
+                                        );
+                                    }
+
+                                    CollapsingHeader::new("消费延迟 (lag)").show(ui, |ui| {
+                                        ui.horizontal(|ui| {
+                                            ui.label("告警阈值:");
+                                            ui.add(egui::DragValue::new(
+                                                &mut self.data_lag_threshold,
+                                            ));
+                                            if ui.button("刷新").clicked() {
+                                                let hosts = self
+                                                    .current_config
+                                                    .host
+                                                    .split(",")
+                                                    .map(|h| h.to_owned())
+                                                    .collect();
+                                                match require_non_sasl_data_plane(
+                                                    self.current_config.security_protocol,
+                                                )
+                                                .and_then(|_| {
+                                                    new_kafka_client(hosts, &self.current_config)
+                                                }) {
+                                                    Err(e) => {
+                                                        self.stream_error_message = e;
+                                                    }
+                                                    Ok(mut client) => {
+                                                        if client
+                                                            .load_metadata(&vec![
+                                                                self.current_topic.clone(),
+                                                            ])
+                                                            .is_ok()
+                                                        {
+                                                            let log_end_offsets = client
+                                                                .fetch_offsets(
+                                                                    &vec![self
+                                                                        .current_topic
+                                                                        .clone()],
+                                                                    FetchOffset::Latest,
+                                                                )
+                                                                .ok()
+                                                                .and_then(|m| {
+                                                                    m.get(
+                                                                        self.current_topic
+                                                                            .as_str(),
+                                                                    )
+                                                                    .cloned()
+                                                                })
+                                                                .unwrap_or_default();
+                                                            let committed = client
+                                                                .fetch_group_offsets(
+                                                                    KAFKA_GROUP_ID,
+                                                                    self.current_topic.as_str(),
+                                                                )
+                                                                .unwrap_or_default();
+
+                                                            self.data_partition_lags =
+                                                                log_end_offsets
+                                                                    .iter()
+                                                                    .map(|po| {
+                                                                        let committed_offset =
+                                                                            committed
+                                                                                .iter()
+                                                                                .find(|c| {
+                                                                                    c.partition
+                                                                                        == po
+                                                                                            .partition
+                                                                                })
+                                                                                .map(|c| c.offset)
+                                                                                .unwrap_or(0);
+                                                                        PartitionLag {
+                                                                            partition: po
+                                                                                .partition,
+                                                                            earliest: 0,
+                                                                            latest: po.offset,
+                                                                            committed:
+                                                                                committed_offset,
+                                                                            lag: po.offset
+                                                                                - committed_offset,
+                                                                        }
+                                                                    })
+                                                                    .collect();
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        });
+                                        let total_lag: i64 =
+                                            self.data_partition_lags.iter().map(|l| l.lag).sum();
+                                        ui.label(format!("总延迟: {}", total_lag));
+                                        egui::Grid::new("data_lag_grid").striped(true).show(
+                                            ui,
+                                            |ui| {
+                                                ui.label("分区");
+                                                ui.label("已提交");
+                                                ui.label("日志末端");
+                                                ui.label("延迟");
+                                                ui.end_row();
+                                                for pl in &self.data_partition_lags {
+                                                    ui.label(pl.partition.to_string());
+                                                    ui.label(pl.committed.to_string());
+                                                    ui.label(pl.latest.to_string());
+                                                    let color = if pl.lag > self.data_lag_threshold
+                                                    {
+                                                        Color32::from_rgb(255, 0, 0)
+                                                    } else {
+                                                        ui.visuals().text_color()
+                                                    };
+                                                    ui.label(
+                                                        RichText::new(pl.lag.to_string())
+                                                            .color(color),
+                                                    );
+                                                    ui.end_row();
+                                                }
+                                            },
+                                        );
+                                    });
+
+                                    // 每帧只编译一次过滤用的正则，而不是每条消息都重新编译一次
+                                    let compiled_filter_regex = if self.filter_is_regex
+                                        && !self.value_filter.trim().is_empty()
+                                    {
+                                        Some(Regex::new(&self.value_filter))
+                                    } else {
+                                        None
+                                    };
+                                    let regex_error =
+                                        compiled_filter_regex.as_ref().and_then(|r| r.as_ref().err());
+                                    let compiled_filter_regex =
+                                        compiled_filter_regex.and_then(|r| r.ok());
+
+                                    ui.horizontal(|ui| {
+                                        ui.checkbox(&mut self.filter_is_regex, "正则");
+                                        ui.checkbox(&mut self.filter_include_key, "同时匹配键");
+                                        ui.label("格式:");
+                                        egui::ComboBox::from_id_source("value_decoder")
+                                            .selected_text(self.value_decoder.label())
+                                            .show_ui(ui, |ui| {
+                                                ui.selectable_value(
+                                                    &mut self.value_decoder,
+                                                    ValueDecoder::Utf8,
+                                                    ValueDecoder::Utf8.label(),
+                                                );
+                                                ui.selectable_value(
+                                                    &mut self.value_decoder,
+                                                    ValueDecoder::Json,
+                                                    ValueDecoder::Json.label(),
+                                                );
+                                                ui.selectable_value(
+                                                    &mut self.value_decoder,
+                                                    ValueDecoder::Hex,
+                                                    ValueDecoder::Hex.label(),
+                                                );
+                                            });
+
+                                        let match_count = self
+                                            .current_messages
+                                            .iter()
+                                            .filter(|km| {
+                                                let decoded =
+                                                    decode_value(&km.value_bytes, self.value_decoder);
+                                                find_filter_match(
+                                                    &decoded,
+                                                    &self.value_filter,
+                                                    self.filter_is_regex,
+                                                    compiled_filter_regex.as_ref(),
+                                                )
+                                                .is_some()
+                                                    || (self.filter_include_key
+                                                        && find_filter_match(
+                                                            &km.key,
+                                                            &self.value_filter,
+                                                            self.filter_is_regex,
+                                                            compiled_filter_regex.as_ref(),
+                                                        )
+                                                        .is_some())
+                                                    || self.value_filter.trim().is_empty()
+                                            })
+                                            .count();
+                                        ui.label(format!("匹配 {} 条", match_count));
+                                        if let Some(e) = regex_error {
+                                            ui.label(
+                                                RichText::new(format!("正则表达式无效: {}", e))
+                                                    .color(Color32::from_rgb(255, 0, 0)),
+                                            );
                                         }
                                     });
 
@@ -570,6 +2032,7 @@ impl eframe::App for ToolApp {
                                         .resizable(true)
                                         .cell_layout(Layout::left_to_right(Align::Center))
                                         .column(Column::auto())
+                                        .column(Column::initial(120.0).range(40.0..=300.0))
                                         .column(Column::initial(100.0).range(40.0..=300.0))
                                         .column(Column::initial(100.0).at_least(40.0).clip(true))
                                         .column(Column::remainder())
@@ -579,6 +2042,9 @@ impl eframe::App for ToolApp {
                                             header.col(|ui| {
                                                 ui.label("序列");
                                             });
+                                            header.col(|ui| {
+                                                ui.label("主题");
+                                            });
                                             header.col(|ui| {
                                                 ui.label("偏移量");
                                             });
@@ -594,13 +2060,33 @@ impl eframe::App for ToolApp {
                                             for (index, km) in
                                                 self.current_messages.iter().enumerate()
                                             {
-                                                if km.value.contains(&self.value_filter)
+                                                let decoded_value =
+                                                    decode_value(&km.value_bytes, self.value_decoder);
+                                                let value_match = find_filter_match(
+                                                    &decoded_value,
+                                                    &self.value_filter,
+                                                    self.filter_is_regex,
+                                                    compiled_filter_regex.as_ref(),
+                                                );
+                                                let key_match = self.filter_include_key
+                                                    && find_filter_match(
+                                                        &km.key,
+                                                        &self.value_filter,
+                                                        self.filter_is_regex,
+                                                        compiled_filter_regex.as_ref(),
+                                                    )
+                                                    .is_some();
+                                                if value_match.is_some()
+                                                    || key_match
                                                     || self.value_filter.trim().is_empty()
                                                 {
                                                     body.row(30.0, |mut row| {
                                                         row.col(|ui| {
                                                             ui.label((index + 1).to_string());
                                                         });
+                                                        row.col(|ui| {
+                                                            ui.label(km.topic.clone());
+                                                        });
                                                         row.col(|ui| {
                                                             ui.label(format!(
                                                                 "{}",
@@ -611,7 +2097,11 @@ impl eframe::App for ToolApp {
                                                             ui.label(km.key.clone());
                                                         });
                                                         row.col(|ui| {
-                                                            ui.label(km.value.clone());
+                                                            render_value_cell(
+                                                                ui,
+                                                                decoded_value.as_str(),
+                                                                value_match,
+                                                            );
                                                         });
                                                     });
                                                 }
@@ -637,6 +2127,55 @@ impl eframe::App for ToolApp {
                         ui.label("地址:");
                         ui.text_edit_singleline(&mut self.temp_config.host);
                     });
+                    ui.horizontal(|ui| {
+                        ui.label("安全协议:");
+                        egui::ComboBox::from_id_source("security_protocol")
+                            .selected_text(self.temp_config.security_protocol.label())
+                            .show_ui(ui, |ui| {
+                                for protocol in [
+                                    SecurityProtocol::Plaintext,
+                                    SecurityProtocol::Ssl,
+                                    SecurityProtocol::SaslPlaintext,
+                                    SecurityProtocol::SaslSsl,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut self.temp_config.security_protocol,
+                                        protocol,
+                                        protocol.label(),
+                                    );
+                                }
+                            });
+                    });
+                    if self.temp_config.security_protocol.uses_sasl() {
+                        ui.horizontal(|ui| {
+                            ui.label("用户名:");
+                            ui.text_edit_singleline(&mut self.temp_config.username);
+                            ui.label("密码:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut self.temp_password_input)
+                                    .password(true),
+                            );
+                        });
+                        ui.label(
+                            egui::RichText::new(
+                                "SASL_PLAINTEXT/SASL_SSL 暂不支持收发消息、位移提交等数据通道操作，\
+                                 仅能用于连接测试和主题列表浏览；请改用 PLAINTEXT/SSL 集群。",
+                            )
+                            .color(egui::Color32::RED),
+                        );
+                    }
+                    if self.temp_config.security_protocol.uses_tls() {
+                        ui.horizontal(|ui| {
+                            ui.label("CA 证书路径:");
+                            ui.text_edit_singleline(&mut self.temp_config.ca_path);
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("客户端证书路径:");
+                            ui.text_edit_singleline(&mut self.temp_config.cert_path);
+                            ui.label("客户端私钥路径:");
+                            ui.text_edit_singleline(&mut self.temp_config.key_path);
+                        });
+                    }
                     match &self.temp_config.message {
                         Some(message) => {
                             ui.label(
@@ -652,6 +2191,13 @@ impl eframe::App for ToolApp {
                             if self.temp_config.id.is_empty() {
                                 self.temp_config.id = uuid::Uuid::new_v4().to_string();
                             }
+                            if !self.temp_password_input.is_empty() {
+                                if let Err(e) =
+                                    self.temp_config.set_password(&self.temp_password_input)
+                                {
+                                    self.temp_config.message = Some(e);
+                                }
+                            }
                             let mut configs = vec![];
                             for item in &self.config.kafka_configs {
                                 if item.id != self.temp_config.id {
@@ -666,9 +2212,19 @@ impl eframe::App for ToolApp {
                             });
                         }
                         if ui.button("测试").clicked() {
-                            let config = &self.temp_config;
-                            let host = &config.host;
-                            let hosts = host.split(",").map(|h| h.to_string()).collect();
+                            if !self.temp_password_input.is_empty() {
+                                if let Err(e) =
+                                    self.temp_config.set_password(&self.temp_password_input)
+                                {
+                                    self.temp_config.message = Some(e);
+                                }
+                            }
+                            let hosts = self
+                                .temp_config
+                                .host
+                                .split(",")
+                                .map(|h| h.to_string())
+                                .collect();
                             load_topics(&hosts, &mut self.temp_config);
                         };
                     });
@@ -678,8 +2234,53 @@ impl eframe::App for ToolApp {
     }
 }
 
+/// 按 `config.security_protocol` 构造 `KafkaClient`：SSL/SASL_SSL 走 TLS 连接，
+/// 其余走明文连接。kafka-rust 本身不支持在连接上叠加 SASL，所以 SASL 的用户名/
+/// 密码校验由 `auth::plain_handshake` 在独立连接上单独完成（见 `load_topics`）。
+/// CA/客户端证书路径来自用户输入，文件不存在或不是合法 PEM 时返回 `Err` 而不是panic。
+fn new_kafka_client(hosts: Vec<String>, config: &KafkaConfig) -> Result<KafkaClient, String> {
+    if config.security_protocol.uses_tls() {
+        let mut builder = SslConnector::builder(SslMethod::tls())
+            .map_err(|e| format!("初始化 TLS 失败: {}", e))?;
+        if !config.ca_path.is_empty() {
+            builder
+                .set_ca_file(&config.ca_path)
+                .map_err(|e| format!("加载 CA 证书 {} 失败: {}", config.ca_path, e))?;
+        }
+        if !config.cert_path.is_empty() && !config.key_path.is_empty() {
+            builder
+                .set_certificate_file(&config.cert_path, SslFiletype::PEM)
+                .map_err(|e| format!("加载客户端证书 {} 失败: {}", config.cert_path, e))?;
+            builder
+                .set_private_key_file(&config.key_path, SslFiletype::PEM)
+                .map_err(|e| format!("加载客户端私钥 {} 失败: {}", config.key_path, e))?;
+        }
+        let connector = builder.build();
+        Ok(KafkaClient::new_secure(
+            hosts,
+            SecurityConfig::new(connector),
+        ))
+    } else {
+        Ok(KafkaClient::new(hosts))
+    }
+}
+
 fn load_topics(hosts: &Vec<String>, config: &mut KafkaConfig) {
-    let mut client = KafkaClient::new(hosts.clone());
+    if config.security_protocol.uses_sasl() {
+        let host = hosts.first().cloned().unwrap_or_default();
+        if let Err(e) = auth::plain_handshake(host.as_str(), &config.username, &config.password()) {
+            config.message = Some(format!("SASL 认证失败: {}!", e));
+            return;
+        }
+    }
+
+    let mut client = match new_kafka_client(hosts.clone(), config) {
+        Ok(client) => client,
+        Err(e) => {
+            config.message = Some(e);
+            return;
+        }
+    };
     match client.load_metadata_all() {
         Ok(_) => {
             let topics: Vec<String> = client.topics().names().map(ToOwned::to_owned).collect();